@@ -1,6 +1,13 @@
 use anyhow::{Context, Result};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
 use octocrab::models::repos::Content;
 use octocrab::Octocrab;
+use std::time::Duration;
+
+const DEFAULT_MAX_RETRIES: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
 
 #[derive(Debug, thiserror::Error, serde::Serialize)]
 pub enum GitHubServiceError {
@@ -10,6 +17,10 @@ pub enum GitHubServiceError {
     Octocrab(String),
     #[error("An internal error occurred: {0}")]
     Anyhow(String),
+    #[error("invalid GitHub App private key: {0}")]
+    InvalidAppKey(String),
+    #[error("rate limited by GitHub after exhausting retries; last backoff was {last_backoff_secs}s (our own estimate, not a Retry-After value from GitHub)")]
+    RateLimited { last_backoff_secs: u64 },
 }
 
 impl From<anyhow::Error> for GitHubServiceError {
@@ -24,10 +35,116 @@ impl From<octocrab::Error> for GitHubServiceError {
     }
 }
 
+// Outcome of `with_retry`: either the underlying octocrab error, or a confirmed
+// rate limit that survived `max_retries` attempts and carries the backoff we
+// actually waited (octocrab's `GitHubError` doesn't expose response headers, so
+// `Retry-After`/`X-RateLimit-Reset` aren't available to derive a better value —
+// `last_backoff_secs` is our own estimate, never a value read off the response).
+enum RetryableError {
+    RateLimited(u64),
+    Other(octocrab::Error),
+}
+
+impl From<RetryableError> for GitHubServiceError {
+    fn from(err: RetryableError) -> Self {
+        match err {
+            RetryableError::RateLimited(last_backoff_secs) => {
+                GitHubServiceError::RateLimited { last_backoff_secs }
+            }
+            RetryableError::Other(e) => e.into(),
+        }
+    }
+}
+
+// A 403 is ambiguous on GitHub: it covers primary/secondary rate limits as well
+// as plain authorization failures (bad scope, blocked content, abuse heuristics),
+// and only the former is worth retrying. 429 is unambiguous. 401 is never treated
+// as retryable here: `from_app`'s installation client is assumed to re-mint its
+// token before it expires, so a 401 would mean that assumption broke, not that
+// waiting and retrying would help.
+//
+// Split out of `is_rate_limit` as a pure function of primitives so the
+// classification itself is unit-testable without constructing an
+// `octocrab::Error::GitHub`.
+fn is_rate_limit_status(status_code: u16, message: &str) -> bool {
+    status_code == 429 || (status_code == 403 && message.to_lowercase().contains("rate limit"))
+}
+
+fn is_rate_limit(err: &octocrab::Error) -> bool {
+    match err {
+        octocrab::Error::GitHub { source, .. } => {
+            is_rate_limit_status(source.status_code.as_u16(), &source.message)
+        }
+        _ => false,
+    }
+}
+
+// Outcome of `retry_with_backoff`: either the underlying error, or confirmation
+// that it was retryable and still failing after `max_retries` attempts, along
+// with the backoff that was waited before giving up.
+enum RetryOutcome<E> {
+    Exhausted(u64),
+    Other(E),
+}
+
+// Decoupled from `octocrab::Error` so the attempt-counting and backoff
+// progression can be exercised with a dummy error type and a fast clock
+// in tests, without needing a real or mocked GitHub response.
+async fn retry_with_backoff<T, E, F, Fut>(
+    max_retries: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    is_retryable: impl Fn(&E) -> bool,
+    mut f: F,
+) -> Result<T, RetryOutcome<E>>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T, E>>,
+{
+    let mut backoff = initial_backoff;
+    let mut last_waited = Duration::ZERO;
+    let mut attempt = 0;
+    loop {
+        match f().await {
+            Ok(value) => return Ok(value),
+            Err(e) if is_retryable(&e) => {
+                if attempt + 1 >= max_retries {
+                    return Err(RetryOutcome::Exhausted(last_waited.as_secs()));
+                }
+                attempt += 1;
+                tokio::time::sleep(backoff).await;
+                last_waited = backoff;
+                backoff = (backoff * 2).min(max_backoff);
+            }
+            Err(e) => return Err(RetryOutcome::Other(e)),
+        }
+    }
+}
+
+// Takes the already-matched sha (`Some` for `Object::Commit`, `None` for any other
+// ref object kind) rather than the raw `octocrab::models::repos::Object` so the
+// branch is unit-testable without constructing octocrab's model types.
+fn commit_sha_from_ref(commit_sha: Option<String>, ref_name: &str) -> Result<String, GitHubServiceError> {
+    commit_sha.ok_or_else(|| anyhow::anyhow!("unexpected ref object type for {ref_name}").into())
+}
+
+fn needs_blob_fallback(content: Option<&str>) -> bool {
+    content.map(str::is_empty).unwrap_or(true)
+}
+
+fn content_unchanged(existing_base64: Option<&str>, desired: &[u8]) -> Result<bool> {
+    let current = existing_base64
+        .map(|encoded| -> Result<Vec<u8>> { Ok(BASE64.decode(encoded.replace('\n', ""))?) })
+        .transpose()?
+        .unwrap_or_default();
+    Ok(current == desired)
+}
+
 pub struct GitHubService {
     client: Octocrab,
     repo_owner: String,
     repo_name: String,
+    max_retries: u32,
 }
 
 impl GitHubService {
@@ -37,9 +154,55 @@ impl GitHubService {
             client,
             repo_owner,
             repo_name,
+            max_retries: DEFAULT_MAX_RETRIES,
         }
     }
 
+    pub fn with_max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    async fn with_retry<T, F, Fut>(&self, f: F) -> Result<T, RetryableError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, octocrab::Error>>,
+    {
+        retry_with_backoff(self.max_retries, INITIAL_BACKOFF, MAX_BACKOFF, is_rate_limit, f)
+            .await
+            .map_err(|e| match e {
+                RetryOutcome::Exhausted(backoff_secs) => RetryableError::RateLimited(backoff_secs),
+                RetryOutcome::Other(err) => RetryableError::Other(err),
+            })
+    }
+
+    pub fn from_app(
+        app_id: u64,
+        private_key_pem: &[u8],
+        installation_id: u64,
+        repo_owner: String,
+        repo_name: String,
+    ) -> Result<Self, GitHubServiceError> {
+        let key = jsonwebtoken::EncodingKey::from_rsa_pem(private_key_pem)
+            .map_err(|e| GitHubServiceError::InvalidAppKey(e.to_string()))?;
+
+        let app_client = Octocrab::builder()
+            .app(app_id.into(), key)
+            .build()?;
+
+        // `installation()` caches the installation token and mints a new one from
+        // the app's JWT once the cached one expires, so an expired token is not
+        // expected to surface as a 401 here.
+        let client = app_client.installation(installation_id.into());
+
+        Ok(Self {
+            client,
+            repo_owner,
+            repo_name,
+            max_retries: DEFAULT_MAX_RETRIES,
+        })
+    }
+
     pub async fn get_content_items(&self, path: &str) -> Result<Vec<Content>> {
         Ok(self.client
             .repos(&self.repo_owner, &self.repo_name)
@@ -50,6 +213,38 @@ impl GitHubService {
             .items)
     }
 
+    pub async fn list_recursive(&self, path: &str) -> Result<Vec<String>> {
+        let mut paths = Vec::new();
+        let mut stack = vec![path.to_string()];
+
+        while let Some(current) = stack.pop() {
+            for item in self.get_content_items(&current).await? {
+                match item.r#type.as_str() {
+                    "dir" => stack.push(item.path),
+                    _ => paths.push(item.path),
+                }
+            }
+        }
+
+        Ok(paths)
+    }
+
+    pub async fn read_file(&self, path: &str) -> Result<Vec<u8>> {
+        let items = self.get_content_items(path).await?;
+        let item = items.first().context("No content found")?;
+
+        if !needs_blob_fallback(item.content.as_deref()) {
+            return Ok(BASE64.decode(item.content.as_deref().unwrap().replace('\n', ""))?);
+        }
+
+        let blob = self
+            .client
+            .repos(&self.repo_owner, &self.repo_name)
+            .get_blob(item.sha.clone())
+            .await?;
+        Ok(BASE64.decode(blob.content.replace('\n', ""))?)
+    }
+
     pub async fn note_exists(&self, path: &str) -> Result<bool> {
         match self.client.repos(&self.repo_owner, &self.repo_name).get_content().path(path).send().await {
             Ok(_) => Ok(true),
@@ -59,28 +254,56 @@ impl GitHubService {
     }
 
     pub async fn create_file(&self, path: &str, message: &str, content: &str) -> Result<(), GitHubServiceError> {
-        match self.client.repos(&self.repo_owner, &self.repo_name).create_file(path, message, content).send().await {
+        match self
+            .with_retry(|| self.client.repos(&self.repo_owner, &self.repo_name).create_file(path, message, content).send())
+            .await
+        {
             Ok(_) => Ok(()),
-            Err(octocrab::Error::GitHub { source, .. }) if source.status_code == 422 => Ok(()), // Race condition
+            Err(RetryableError::Other(octocrab::Error::GitHub { source, .. })) if source.status_code == 422 => Ok(()), // Race condition
             Err(e) => Err(e.into()),
         }
     }
 
-    pub async fn update_file(&self, path: &str, message: &str, content: &str, sha: &str) -> Result<()> {
-        self.client
-            .repos(&self.repo_owner, &self.repo_name)
-            .update_file(path, message, content, sha)
-            .send()
-            .await?;
+    pub async fn update_file(&self, path: &str, message: &str, content: &str, sha: &str) -> Result<(), GitHubServiceError> {
+        self.with_retry(|| {
+            self.client
+                .repos(&self.repo_owner, &self.repo_name)
+                .update_file(path, message, content, sha)
+                .send()
+        })
+        .await?;
         Ok(())
     }
 
-    pub async fn delete_file(&self, path: &str, message: &str, sha: &str) -> Result<()> {
-        self.client
-            .repos(&self.repo_owner, &self.repo_name)
-            .delete_file(path, message, sha)
-            .send()
-            .await?;
+    pub async fn put_file(&self, path: &str, message: &str, content: &str) -> Result<(), GitHubServiceError> {
+        match self
+            .with_retry(|| self.client.repos(&self.repo_owner, &self.repo_name).create_file(path, message, content).send())
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(RetryableError::Other(octocrab::Error::GitHub { source, .. })) if source.status_code == 422 => {
+                let existing = self.get_content_items(path).await?;
+                let item = existing.first().context("No content found")?;
+
+                if content_unchanged(item.content.as_deref(), content.as_bytes())? {
+                    return Ok(());
+                }
+
+                self.update_file(path, message, content, &item.sha).await?;
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn delete_file(&self, path: &str, message: &str, sha: &str) -> Result<(), GitHubServiceError> {
+        self.with_retry(|| {
+            self.client
+                .repos(&self.repo_owner, &self.repo_name)
+                .delete_file(path, message, sha)
+                .send()
+        })
+        .await?;
         Ok(())
     }
 
@@ -89,4 +312,267 @@ impl GitHubService {
         let sha = content.first().context("No content found")?.sha.clone();
         Ok(sha)
     }
+
+    pub async fn commit_files(
+        &self,
+        branch: &str,
+        message: &str,
+        files: Vec<(String, String)>,
+    ) -> Result<(), GitHubServiceError> {
+        let repos = self.client.repos(&self.repo_owner, &self.repo_name);
+
+        let reference = self
+            .with_retry(|| repos.get_ref(&octocrab::params::repos::Reference::Branch(branch.to_string())))
+            .await?;
+        let commit_sha = commit_sha_from_ref(
+            match reference.object {
+                octocrab::models::repos::Object::Commit { sha, .. } => Some(sha),
+                _ => None,
+            },
+            branch,
+        )?;
+
+        let base_commit = self
+            .with_retry(|| {
+                self.client.get::<octocrab::models::repos::Commit, _, ()>(
+                    format!(
+                        "/repos/{}/{}/git/commits/{commit_sha}",
+                        self.repo_owner, self.repo_name
+                    ),
+                    None,
+                )
+            })
+            .await?;
+        let base_tree_sha = base_commit.tree.sha;
+
+        let mut tree_entries = Vec::with_capacity(files.len());
+        for (path, content) in files {
+            let blob = self
+                .with_retry(|| {
+                    self.client
+                        .repos(&self.repo_owner, &self.repo_name)
+                        .create_blob(content.clone())
+                        .send()
+                })
+                .await?;
+            tree_entries.push(
+                octocrab::models::repos::GitTreeEntry::builder()
+                    .path(path)
+                    .mode("100644".to_string())
+                    .entry_type("blob".to_string())
+                    .sha(blob.sha)
+                    .build(),
+            );
+        }
+
+        let new_tree = self
+            .with_retry(|| {
+                self.client
+                    .repos(&self.repo_owner, &self.repo_name)
+                    .create_tree(&tree_entries)
+                    .base_tree(base_tree_sha.clone())
+                    .send()
+            })
+            .await?;
+
+        let new_commit = self
+            .with_retry(|| {
+                self.client
+                    .repos(&self.repo_owner, &self.repo_name)
+                    .create_git_commit_object(message, new_tree.sha.clone())
+                    .parents(vec![commit_sha.clone()])
+                    .send()
+            })
+            .await?;
+
+        self.with_retry(|| {
+            self.client.repos(&self.repo_owner, &self.repo_name).update_ref(
+                &octocrab::params::repos::Reference::Branch(branch.to_string()),
+                new_commit.sha.clone(),
+            )
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn create_branch(&self, new_branch: &str, from_branch: &str) -> Result<(), GitHubServiceError> {
+        let reference = self
+            .with_retry(|| {
+                self.client.repos(&self.repo_owner, &self.repo_name).get_ref(
+                    &octocrab::params::repos::Reference::Branch(from_branch.to_string()),
+                )
+            })
+            .await?;
+        let sha = commit_sha_from_ref(
+            match reference.object {
+                octocrab::models::repos::Object::Commit { sha, .. } => Some(sha),
+                _ => None,
+            },
+            from_branch,
+        )?;
+
+        self.with_retry(|| {
+            self.client.repos(&self.repo_owner, &self.repo_name).create_ref(
+                &octocrab::params::repos::Reference::Branch(new_branch.to_string()),
+                sha.clone(),
+            )
+        })
+        .await?;
+
+        Ok(())
+    }
+
+    // open_pull_request and list_open_pull_requests are thin wrappers around
+    // octocrab's pulls handler with no branching logic of their own to extract
+    // and test in isolation; create_branch's ref-object handling above is
+    // covered via the shared, tested commit_sha_from_ref.
+    pub async fn open_pull_request(&self, title: &str, head: &str, base: &str, body: &str) -> Result<u64, GitHubServiceError> {
+        let pr = self
+            .with_retry(|| {
+                self.client
+                    .pulls(&self.repo_owner, &self.repo_name)
+                    .create(title, head, base)
+                    .body(body)
+                    .send()
+            })
+            .await?;
+        Ok(pr.number)
+    }
+
+    pub async fn list_open_pull_requests(&self) -> Result<Vec<(u64, String, Vec<String>)>, GitHubServiceError> {
+        let pulls = self.client.pulls(&self.repo_owner, &self.repo_name);
+        let open_prs = self
+            .with_retry(|| pulls.list().state(octocrab::params::State::Open).send())
+            .await?;
+
+        let mut result = Vec::with_capacity(open_prs.items.len());
+        for pr in open_prs.items {
+            let files = self
+                .with_retry(|| pulls.list_files(pr.number))
+                .await?
+                .items
+                .into_iter()
+                .map(|f| f.filename)
+                .collect();
+            result.push((pr.number, pr.head.ref_field, files));
+        }
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_unchanged_true_when_existing_matches() {
+        let encoded = BASE64.encode(b"hello");
+        assert!(content_unchanged(Some(&encoded), b"hello").unwrap());
+    }
+
+    #[test]
+    fn content_unchanged_false_when_existing_differs() {
+        let encoded = BASE64.encode(b"hello");
+        assert!(!content_unchanged(Some(&encoded), b"goodbye").unwrap());
+    }
+
+    #[test]
+    fn content_unchanged_false_when_nothing_exists_yet() {
+        assert!(!content_unchanged(None, b"hello").unwrap());
+    }
+
+    #[test]
+    fn needs_blob_fallback_false_when_inline_content_present() {
+        assert!(!needs_blob_fallback(Some("aGVsbG8=")));
+    }
+
+    #[test]
+    fn needs_blob_fallback_true_when_content_empty() {
+        assert!(needs_blob_fallback(Some("")));
+    }
+
+    #[test]
+    fn needs_blob_fallback_true_when_content_missing() {
+        assert!(needs_blob_fallback(None));
+    }
+
+    #[test]
+    fn is_rate_limit_status_treats_a_secondary_rate_limit_403_as_retryable() {
+        assert!(is_rate_limit_status(
+            403,
+            "You have exceeded a secondary rate limit. Please wait a few minutes."
+        ));
+    }
+
+    #[test]
+    fn is_rate_limit_status_does_not_treat_a_plain_403_as_retryable() {
+        assert!(!is_rate_limit_status(403, "Resource not accessible by integration"));
+    }
+
+    #[test]
+    fn is_rate_limit_status_always_treats_429_as_retryable() {
+        assert!(is_rate_limit_status(429, ""));
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_gives_up_after_exactly_max_retries_attempts() {
+        use std::cell::Cell;
+
+        let max_retries = 3;
+        let calls = Cell::new(0u32);
+        let result = retry_with_backoff(
+            max_retries,
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+            |_: &&str| true,
+            || {
+                calls.set(calls.get() + 1);
+                async { Err::<(), &str>("rate limited") }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(RetryOutcome::Exhausted(_))));
+        assert_eq!(calls.get(), max_retries);
+    }
+
+    #[tokio::test]
+    async fn retry_with_backoff_returns_immediately_for_non_retryable_errors() {
+        let calls = std::cell::Cell::new(0u32);
+        let result = retry_with_backoff(
+            5,
+            Duration::from_millis(1),
+            Duration::from_millis(1),
+            |_: &&str| false,
+            || {
+                calls.set(calls.get() + 1);
+                async { Err::<(), &str>("permission denied") }
+            },
+        )
+        .await;
+
+        assert!(matches!(result, Err(RetryOutcome::Other("permission denied"))));
+        assert_eq!(calls.get(), 1);
+    }
+
+    #[test]
+    fn commit_sha_from_ref_returns_the_sha_for_a_commit_object() {
+        assert_eq!(
+            commit_sha_from_ref(Some("abc123".to_string()), "main").unwrap(),
+            "abc123"
+        );
+    }
+
+    #[test]
+    fn commit_sha_from_ref_errors_on_an_unexpected_ref_object_type() {
+        let result = commit_sha_from_ref(None, "main");
+        assert!(matches!(result, Err(GitHubServiceError::Anyhow(_))));
+    }
+
+    #[test]
+    fn from_app_rejects_a_malformed_private_key() {
+        let result = GitHubService::from_app(1, b"not a pem key", 1, "owner".to_string(), "repo".to_string());
+        assert!(matches!(result, Err(GitHubServiceError::InvalidAppKey(_))));
+    }
 }
\ No newline at end of file
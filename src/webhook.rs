@@ -0,0 +1,179 @@
+use hmac::{Hmac, Mac};
+use serde::Deserialize;
+use sha2::Sha256;
+use subtle::ConstantTimeEq;
+
+#[derive(Debug, thiserror::Error, serde::Serialize)]
+pub enum WebhookError {
+    #[error("malformed X-Hub-Signature-256 header")]
+    MalformedSignature,
+    #[error("unsupported or malformed event payload: {0}")]
+    InvalidPayload(String),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GitHubEvent {
+    Push {
+        after: String,
+        repo_full_name: String,
+        pusher: String,
+    },
+    PullRequest {
+        action: String,
+        number: u64,
+    },
+    Other,
+}
+
+pub fn verify_signature(secret: &[u8], signature_header: &str, body: &[u8]) -> bool {
+    let Some(expected_hex) = signature_header.strip_prefix("sha256=") else {
+        return false;
+    };
+    let Ok(expected) = hex::decode(expected_hex) else {
+        return false;
+    };
+
+    let mut mac = match Hmac::<Sha256>::new_from_slice(secret) {
+        Ok(mac) => mac,
+        Err(_) => return false,
+    };
+    mac.update(body);
+    let computed = mac.finalize().into_bytes();
+
+    if computed.len() != expected.len() {
+        return false;
+    }
+    computed.ct_eq(&expected).into()
+}
+
+pub fn parse_event(event_name: &str, body: &[u8]) -> Result<GitHubEvent, WebhookError> {
+    match event_name {
+        "push" => {
+            #[derive(Deserialize)]
+            struct Pusher {
+                name: String,
+            }
+            #[derive(Deserialize)]
+            struct Repository {
+                full_name: String,
+            }
+            #[derive(Deserialize)]
+            struct PushPayload {
+                after: String,
+                repository: Repository,
+                pusher: Pusher,
+            }
+            let payload: PushPayload = serde_json::from_slice(body)
+                .map_err(|e| WebhookError::InvalidPayload(e.to_string()))?;
+            Ok(GitHubEvent::Push {
+                after: payload.after,
+                repo_full_name: payload.repository.full_name,
+                pusher: payload.pusher.name,
+            })
+        }
+        "pull_request" => {
+            #[derive(Deserialize)]
+            struct PullRequestPayload {
+                action: String,
+                number: u64,
+            }
+            let payload: PullRequestPayload = serde_json::from_slice(body)
+                .map_err(|e| WebhookError::InvalidPayload(e.to_string()))?;
+            Ok(GitHubEvent::PullRequest {
+                action: payload.action,
+                number: payload.number,
+            })
+        }
+        _ => Ok(GitHubEvent::Other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sign(secret: &[u8], body: &[u8]) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret).unwrap();
+        mac.update(body);
+        format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+    }
+
+    #[test]
+    fn verify_signature_accepts_a_valid_signature() {
+        let secret = b"secret";
+        let body = b"{\"ok\":true}";
+        let header = sign(secret, body);
+        assert!(verify_signature(secret, &header, body));
+    }
+
+    #[test]
+    fn verify_signature_rejects_a_bit_flipped_signature() {
+        let secret = b"secret";
+        let body = b"{\"ok\":true}";
+        let mut header = sign(secret, body);
+        let last = header.pop().unwrap();
+        header.push(if last == '0' { '1' } else { '0' });
+        assert!(!verify_signature(secret, &header, body));
+    }
+
+    #[test]
+    fn verify_signature_rejects_missing_sha256_prefix() {
+        let secret = b"secret";
+        let body = b"{\"ok\":true}";
+        let header = sign(secret, body).replace("sha256=", "");
+        assert!(!verify_signature(secret, &header, body));
+    }
+
+    #[test]
+    fn verify_signature_rejects_non_hex_digest() {
+        let secret = b"secret";
+        let body = b"{\"ok\":true}";
+        assert!(!verify_signature(secret, "sha256=not-hex-zzzz", body));
+    }
+
+    #[test]
+    fn verify_signature_rejects_wrong_length_digest() {
+        let secret = b"secret";
+        let body = b"{\"ok\":true}";
+        let mut header = sign(secret, body);
+        header.truncate(header.len() - 2);
+        assert!(!verify_signature(secret, &header, body));
+    }
+
+    #[test]
+    fn parse_event_decodes_push() {
+        let body = br#"{"after":"abc123","repository":{"full_name":"octo/repo"},"pusher":{"name":"octocat"}}"#;
+        let event = parse_event("push", body).unwrap();
+        assert_eq!(
+            event,
+            GitHubEvent::Push {
+                after: "abc123".to_string(),
+                repo_full_name: "octo/repo".to_string(),
+                pusher: "octocat".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_event_decodes_pull_request() {
+        let body = br#"{"action":"opened","number":42}"#;
+        let event = parse_event("pull_request", body).unwrap();
+        assert_eq!(
+            event,
+            GitHubEvent::PullRequest {
+                action: "opened".to_string(),
+                number: 42,
+            }
+        );
+    }
+
+    #[test]
+    fn parse_event_falls_back_to_other_for_unknown_events() {
+        assert_eq!(parse_event("star", b"{}").unwrap(), GitHubEvent::Other);
+    }
+
+    #[test]
+    fn parse_event_rejects_malformed_payload() {
+        assert!(parse_event("push", b"{}").is_err());
+    }
+}